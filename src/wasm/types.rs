@@ -1,20 +1,115 @@
-use super::BlockType;
 use std::convert::From;
 
 pub trait Type {}
 
+pub trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+pub type TypeId = u32;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeapType {
+    Func,
+    Extern,
+    Any,
+    None,
+    NoFunc,
+    NoExtern,
+    NoExn,
+    Concrete(u32),
+}
+
+impl HeapType {
+    pub fn decode(buf: &mut &[u8]) -> Self {
+        match buf[0] {
+            0x70 => {
+                *buf = &buf[1..];
+                HeapType::Func
+            }
+            0x6F => {
+                *buf = &buf[1..];
+                HeapType::Extern
+            }
+            0x6E => {
+                *buf = &buf[1..];
+                HeapType::Any
+            }
+            0x71 => {
+                *buf = &buf[1..];
+                HeapType::None
+            }
+            0x73 => {
+                *buf = &buf[1..];
+                HeapType::NoFunc
+            }
+            0x72 => {
+                *buf = &buf[1..];
+                HeapType::NoExtern
+            }
+            0x74 => {
+                *buf = &buf[1..];
+                HeapType::NoExn
+            }
+            _ => {
+                let idx = read_var_i32(buf);
+                assert!(idx >= 0, "unsupported heap type");
+                HeapType::Concrete(idx as u32)
+            }
+        }
+    }
+}
+
+impl Encode for HeapType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            HeapType::Func => out.push(0x70),
+            HeapType::Extern => out.push(0x6F),
+            HeapType::Any => out.push(0x6E),
+            HeapType::None => out.push(0x71),
+            HeapType::NoFunc => out.push(0x73),
+            HeapType::NoExtern => out.push(0x72),
+            HeapType::NoExn => out.push(0x74),
+            HeapType::Concrete(id) => write_var_i32(out, *id as i32),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RefType {
+    pub nullable: bool,
+    pub heap: HeapType,
+}
+
+impl Encode for RefType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        if self.nullable {
+            match self.heap {
+                HeapType::Func => return out.push(0x70),
+                HeapType::Extern => return out.push(0x6F),
+                HeapType::None => return out.push(0x71),
+                HeapType::NoFunc => return out.push(0x73),
+                HeapType::NoExtern => return out.push(0x72),
+                HeapType::NoExn => return out.push(0x74),
+                HeapType::Any | HeapType::Concrete(_) => out.push(0x63),
+            }
+        } else {
+            out.push(0x64);
+        }
+        self.heap.encode(out);
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum ValueType {
-    None = 0,
-    Any = 1,
-    I32 = 2,
-    I64 = 3,
-    F32 = 4,
-    F64 = 5,
-    V128 = 6,
-    AnyRef = 7,
-    AnyFunc = 8,
-    NullRef = 9,
+    None,
+    Any,
+    I32,
+    I64,
+    F32,
+    F64,
+    V128,
+    Ref(RefType),
 }
 
 impl Default for ValueType {
@@ -26,19 +121,136 @@ impl Default for ValueType {
 impl Type for ValueType {}
 
 impl ValueType {
-    pub const LENGTH: usize = 10;
+    pub const LENGTH: usize = 8;
 
     pub fn get_bytes(&self) -> u32 {
         match self {
             ValueType::I32 | ValueType::F32 => 4,
             ValueType::I64 | ValueType::F64 => 8,
             ValueType::V128 => 16,
-            ValueType::AnyFunc | ValueType::AnyRef | ValueType::NullRef => 8,
+            ValueType::Ref(_) => 8,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn decode(buf: &mut &[u8]) -> Self {
+        match buf[0] {
+            0x7F => {
+                *buf = &buf[1..];
+                ValueType::I32
+            }
+            0x7E => {
+                *buf = &buf[1..];
+                ValueType::I64
+            }
+            0x7D => {
+                *buf = &buf[1..];
+                ValueType::F32
+            }
+            0x7C => {
+                *buf = &buf[1..];
+                ValueType::F64
+            }
+            0x7B => {
+                *buf = &buf[1..];
+                ValueType::V128
+            }
+            0x70 => {
+                *buf = &buf[1..];
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::Func })
+            }
+            0x6F => {
+                *buf = &buf[1..];
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::Extern })
+            }
+            0x71 => {
+                *buf = &buf[1..];
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::None })
+            }
+            0x73 => {
+                *buf = &buf[1..];
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::NoFunc })
+            }
+            0x72 => {
+                *buf = &buf[1..];
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::NoExtern })
+            }
+            0x74 => {
+                *buf = &buf[1..];
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::NoExn })
+            }
+            0x63 => {
+                *buf = &buf[1..];
+                let heap = HeapType::decode(buf);
+                ValueType::Ref(RefType { nullable: true, heap })
+            }
+            0x64 => {
+                *buf = &buf[1..];
+                let heap = HeapType::decode(buf);
+                ValueType::Ref(RefType { nullable: false, heap })
+            }
             _ => unreachable!(),
         }
     }
 }
 
+impl Encode for ValueType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            ValueType::I32 => out.push(0x7F),
+            ValueType::I64 => out.push(0x7E),
+            ValueType::F32 => out.push(0x7D),
+            ValueType::F64 => out.push(0x7C),
+            ValueType::V128 => out.push(0x7B),
+            ValueType::Ref(r) => r.encode(out),
+            ValueType::None | ValueType::Any => unreachable!(),
+        }
+    }
+}
+
+fn read_var_i32(buf: &mut &[u8]) -> i32 {
+    let mut result: i32 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = buf[0];
+        *buf = &buf[1..];
+        result |= ((byte & 0x7F) as i32) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 32 && (byte & 0x40) != 0 {
+                result |= !0i32 << shift;
+            }
+            break;
+        }
+    }
+    result
+}
+
+fn write_var_i32(out: &mut Vec<u8>, mut value: i32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn write_var_u32(out: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
 impl From<parity_wasm::elements::ValueType> for ValueType {
     fn from(ty: parity_wasm::elements::ValueType) -> Self {
         match ty {
@@ -51,11 +263,19 @@ impl From<parity_wasm::elements::ValueType> for ValueType {
     }
 }
 
-impl From<BlockType> for ValueType {
-    fn from(ty: BlockType) -> Self {
-        match ty {
-            BlockType::Value(v) => ValueType::from(v),
-            BlockType::NoResult => ValueType::None,
+#[derive(Copy, Clone, Debug)]
+pub enum BlockType {
+    Value(ValueType),
+    NoResult,
+    MultiValue(TypeId),
+}
+
+impl BlockType {
+    pub fn results(&self, types: &[FunctionType]) -> Vec<ValueType> {
+        match self {
+            BlockType::NoResult => Vec::new(),
+            BlockType::Value(v) => vec![*v],
+            BlockType::MultiValue(id) => types[*id as usize].res().to_vec(),
         }
     }
 }
@@ -95,10 +315,40 @@ pub union V128 {
     u8x16: [u8; 16],
     i16x8: [i16; 8],
     u16x8: [u16; 8],
-    i32x8: [i32; 4],
-    u32x8: [u32; 4],
+    i32x4: [i32; 4],
+    u32x4: [u32; 4],
     i64x2: [i64; 2],
     u64x2: [u64; 2],
+    f32x4: [f32; 4],
+    f64x2: [f64; 2],
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Shape {
+    I8x16,
+    U8x16,
+    I16x8,
+    U16x8,
+    I32x4,
+    U32x4,
+    I64x2,
+    U64x2,
+    F32x4,
+    F64x2,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Lane {
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
 }
 
 impl Type for I32 {}
@@ -125,8 +375,118 @@ impl V128 {
     }
 
     pub fn into_u64x2(&self) -> [u64; 2] {
+        self.as_u64x2()
+    }
+
+    pub fn as_i8x16(&self) -> [i8; 16] {
+        unsafe { self.i8x16 }
+    }
+
+    pub fn as_u8x16(&self) -> [u8; 16] {
+        unsafe { self.u8x16 }
+    }
+
+    pub fn as_i16x8(&self) -> [i16; 8] {
+        unsafe { self.i16x8 }
+    }
+
+    pub fn as_u16x8(&self) -> [u16; 8] {
+        unsafe { self.u16x8 }
+    }
+
+    pub fn as_i32x4(&self) -> [i32; 4] {
+        unsafe { self.i32x4 }
+    }
+
+    pub fn as_u32x4(&self) -> [u32; 4] {
+        unsafe { self.u32x4 }
+    }
+
+    pub fn as_i64x2(&self) -> [i64; 2] {
+        unsafe { self.i64x2 }
+    }
+
+    pub fn as_u64x2(&self) -> [u64; 2] {
         unsafe { self.u64x2 }
     }
+
+    pub fn as_f32x4(&self) -> [f32; 4] {
+        unsafe { self.f32x4 }
+    }
+
+    pub fn as_f64x2(&self) -> [f64; 2] {
+        unsafe { self.f64x2 }
+    }
+
+    pub fn from_i8x16(lanes: [i8; 16]) -> Self {
+        Self { i8x16: lanes }
+    }
+
+    pub fn from_u8x16(lanes: [u8; 16]) -> Self {
+        Self { u8x16: lanes }
+    }
+
+    pub fn from_i16x8(lanes: [i16; 8]) -> Self {
+        Self { i16x8: lanes }
+    }
+
+    pub fn from_u16x8(lanes: [u16; 8]) -> Self {
+        Self { u16x8: lanes }
+    }
+
+    pub fn from_i32x4(lanes: [i32; 4]) -> Self {
+        Self { i32x4: lanes }
+    }
+
+    pub fn from_u32x4(lanes: [u32; 4]) -> Self {
+        Self { u32x4: lanes }
+    }
+
+    pub fn from_i64x2(lanes: [i64; 2]) -> Self {
+        Self { i64x2: lanes }
+    }
+
+    pub fn from_u64x2(lanes: [u64; 2]) -> Self {
+        Self { u64x2: lanes }
+    }
+
+    pub fn from_f32x4(lanes: [f32; 4]) -> Self {
+        Self { f32x4: lanes }
+    }
+
+    pub fn from_f64x2(lanes: [f64; 2]) -> Self {
+        Self { f64x2: lanes }
+    }
+
+    pub fn splat(lane: Lane) -> Self {
+        match lane {
+            Lane::I8(v) => Self::from_i8x16([v; 16]),
+            Lane::U8(v) => Self::from_u8x16([v; 16]),
+            Lane::I16(v) => Self::from_i16x8([v; 8]),
+            Lane::U16(v) => Self::from_u16x8([v; 8]),
+            Lane::I32(v) => Self::from_i32x4([v; 4]),
+            Lane::U32(v) => Self::from_u32x4([v; 4]),
+            Lane::I64(v) => Self::from_i64x2([v; 2]),
+            Lane::U64(v) => Self::from_u64x2([v; 2]),
+            Lane::F32(v) => Self::from_f32x4([v; 4]),
+            Lane::F64(v) => Self::from_f64x2([v; 2]),
+        }
+    }
+
+    pub fn lane(&self, shape: Shape, idx: usize) -> Lane {
+        match shape {
+            Shape::I8x16 => Lane::I8(self.as_i8x16()[idx]),
+            Shape::U8x16 => Lane::U8(self.as_u8x16()[idx]),
+            Shape::I16x8 => Lane::I16(self.as_i16x8()[idx]),
+            Shape::U16x8 => Lane::U16(self.as_u16x8()[idx]),
+            Shape::I32x4 => Lane::I32(self.as_i32x4()[idx]),
+            Shape::U32x4 => Lane::U32(self.as_u32x4()[idx]),
+            Shape::I64x2 => Lane::I64(self.as_i64x2()[idx]),
+            Shape::U64x2 => Lane::U64(self.as_u64x2()[idx]),
+            Shape::F32x4 => Lane::F32(self.as_f32x4()[idx]),
+            Shape::F64x2 => Lane::F64(self.as_f64x2()[idx]),
+        }
+    }
 }
 
 impl From<Box<[u8; 16]>> for V128 {
@@ -162,9 +522,16 @@ impl GlobalType {
     }
 }
 
+impl Encode for GlobalType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.ty.encode(out);
+        out.push(if self.mutable { 0x01 } else { 0x00 });
+    }
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct FunctionType {
-    res: Option<ValueType>,
+    results: Vec<ValueType>,
     params: Vec<ValueType>,
 }
 
@@ -172,27 +539,27 @@ impl Type for FunctionType {}
 
 impl From<parity_wasm::elements::FunctionType> for FunctionType {
     fn from(func_type: parity_wasm::elements::FunctionType) -> Self {
-        let res = if let Some(res_type) = func_type.return_type() {
-            Some(ValueType::from(res_type))
-        } else {
-            None
-        };
+        let results = func_type
+            .results()
+            .iter()
+            .map(|t| ValueType::from(*t))
+            .collect();
         let params = func_type
             .params()
             .iter()
             .map(|t| ValueType::from(*t))
             .collect();
 
-        Self { res, params }
+        Self { results, params }
     }
 }
 
 impl FunctionType {
-    pub fn new(params: Vec<ValueType>, res: Option<ValueType>) -> Self {
-        Self { res, params }
+    pub fn new(params: Vec<ValueType>, results: Vec<ValueType>) -> Self {
+        Self { results, params }
     }
-    pub fn res(&self) -> Option<ValueType> {
-        self.res
+    pub fn res(&self) -> &[ValueType] {
+        &self.results
     }
 
     pub fn params(&self) -> &[ValueType] {
@@ -200,6 +567,86 @@ impl FunctionType {
     }
 }
 
+impl Encode for FunctionType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(0x60);
+        write_var_u32(out, self.params.len() as u32);
+        for p in &self.params {
+            p.encode(out);
+        }
+        write_var_u32(out, self.results.len() as u32);
+        for r in &self.results {
+            r.encode(out);
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum StorageType {
+    Val(ValueType),
+    I8,
+    I16,
+}
+
+impl StorageType {
+    pub fn get_bytes(&self) -> u32 {
+        match self {
+            StorageType::I8 => 1,
+            StorageType::I16 => 2,
+            StorageType::Val(v) => v.get_bytes(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct FieldType {
+    pub storage: StorageType,
+    pub mutable: bool,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct StructType {
+    fields: Vec<FieldType>,
+}
+
+impl Type for StructType {}
+
+impl StructType {
+    pub fn new(fields: Vec<FieldType>) -> Self {
+        Self { fields }
+    }
+
+    pub fn fields(&self) -> &[FieldType] {
+        &self.fields
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ArrayType {
+    field: FieldType,
+}
+
+impl Type for ArrayType {}
+
+impl ArrayType {
+    pub fn new(field: FieldType) -> Self {
+        Self { field }
+    }
+
+    pub fn field(&self) -> FieldType {
+        self.field
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum CompositeType {
+    Func(FunctionType),
+    Struct(StructType),
+    Array(ArrayType),
+}
+
+impl Type for CompositeType {}
+
 #[derive(Clone, Copy)]
 pub struct MemoryType {
     min: u32,
@@ -225,3 +672,277 @@ impl MemoryType {
 
     pub fn is_shared(&self) -> bool { self.shared }
 }
+
+impl Encode for MemoryType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let flag: u8 = if self.shared {
+            0x03
+        } else if self.max.is_some() {
+            0x01
+        } else {
+            0x00
+        };
+        out.push(flag);
+        write_var_u32(out, self.min);
+        if let Some(max) = self.max {
+            write_var_u32(out, max);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TableType {
+    elem: ValueType,
+    min: u32,
+    max: Option<u32>,
+}
+
+impl Type for TableType {}
+
+impl From<parity_wasm::elements::TableType> for TableType {
+    fn from(table_type: parity_wasm::elements::TableType) -> Self {
+        let elem = match table_type.elem_type() {
+            parity_wasm::elements::TableElementType::AnyFunc => {
+                ValueType::Ref(RefType { nullable: true, heap: HeapType::Func })
+            }
+        };
+        let min = table_type.limits().initial();
+        let max = table_type.limits().maximum();
+        Self { elem, min, max }
+    }
+}
+
+impl TableType {
+    pub fn element_type(&self) -> ValueType { self.elem }
+
+    pub fn min_elements(&self) -> u32 { self.min }
+
+    pub fn max_elements(&self) -> Option<u32> { self.max }
+}
+
+impl Encode for TableType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.elem.encode(out);
+        let flag: u8 = if self.max.is_some() { 0x01 } else { 0x00 };
+        out.push(flag);
+        write_var_u32(out, self.min);
+        if let Some(max) = self.max {
+            write_var_u32(out, max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(ty: ValueType, bytes: &[u8]) {
+        let mut encoded = Vec::new();
+        ty.encode(&mut encoded);
+        assert_eq!(encoded, bytes);
+
+        let mut slice = bytes;
+        let decoded = ValueType::decode(&mut slice);
+        assert!(slice.is_empty());
+
+        let mut re_encoded = Vec::new();
+        decoded.encode(&mut re_encoded);
+        assert_eq!(re_encoded, bytes);
+    }
+
+    #[test]
+    fn value_type_numeric_roundtrip() {
+        roundtrip(ValueType::I32, &[0x7F]);
+        roundtrip(ValueType::I64, &[0x7E]);
+        roundtrip(ValueType::F32, &[0x7D]);
+        roundtrip(ValueType::F64, &[0x7C]);
+        roundtrip(ValueType::V128, &[0x7B]);
+    }
+
+    #[test]
+    fn value_type_abbreviated_ref_roundtrip() {
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::Func }), &[0x70]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::Extern }), &[0x6F]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::None }), &[0x71]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::NoFunc }), &[0x73]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::NoExtern }), &[0x72]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::NoExn }), &[0x74]);
+    }
+
+    #[test]
+    fn value_type_explicit_ref_roundtrip() {
+        roundtrip(ValueType::Ref(RefType { nullable: false, heap: HeapType::Func }), &[0x64, 0x70]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::Any }), &[0x63, 0x6E]);
+        roundtrip(ValueType::Ref(RefType { nullable: true, heap: HeapType::Concrete(5) }), &[0x63, 0x05]);
+        roundtrip(ValueType::Ref(RefType { nullable: false, heap: HeapType::Concrete(5) }), &[0x64, 0x05]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn heap_type_decode_rejects_unsupported_byte() {
+        let mut slice: &[u8] = &[0x6D];
+        HeapType::decode(&mut slice);
+    }
+
+    #[test]
+    fn memory_type_limits_flag_encode() {
+        let cases = [
+            (MemoryType { min: 1, max: None, shared: false }, vec![0x00, 0x01]),
+            (MemoryType { min: 1, max: Some(2), shared: false }, vec![0x01, 0x01, 0x02]),
+            (MemoryType { min: 1, max: Some(2), shared: true }, vec![0x03, 0x01, 0x02]),
+        ];
+        for (ty, bytes) in cases {
+            let mut encoded = Vec::new();
+            ty.encode(&mut encoded);
+            assert_eq!(encoded, bytes);
+        }
+    }
+
+    #[test]
+    fn table_type_limits_flag_encode() {
+        let func_ref = ValueType::Ref(RefType { nullable: true, heap: HeapType::Func });
+        let cases = [
+            (TableType { elem: func_ref, min: 1, max: None }, vec![0x70, 0x00, 0x01]),
+            (TableType { elem: func_ref, min: 1, max: Some(4) }, vec![0x70, 0x01, 0x01, 0x04]),
+        ];
+        for (ty, bytes) in cases {
+            let mut encoded = Vec::new();
+            ty.encode(&mut encoded);
+            assert_eq!(encoded, bytes);
+        }
+    }
+
+    #[test]
+    fn function_type_encode() {
+        let ty = FunctionType::new(
+            vec![ValueType::I32, ValueType::I64],
+            vec![ValueType::F32, ValueType::F64],
+        );
+        let mut encoded = Vec::new();
+        ty.encode(&mut encoded);
+        assert_eq!(encoded, vec![0x60, 0x02, 0x7F, 0x7E, 0x02, 0x7D, 0x7C]);
+    }
+
+    #[test]
+    fn global_type_encode() {
+        let cases = [
+            (GlobalType { ty: ValueType::I32, mutable: false }, vec![0x7F, 0x00]),
+            (GlobalType { ty: ValueType::I32, mutable: true }, vec![0x7F, 0x01]),
+        ];
+        for (ty, bytes) in cases {
+            let mut encoded = Vec::new();
+            ty.encode(&mut encoded);
+            assert_eq!(encoded, bytes);
+        }
+    }
+
+    #[test]
+    fn v128_i8x16_roundtrip() {
+        let lanes = [1i8, -2, 3, -4, 5, -6, 7, -8, 9, -10, 11, -12, 13, -14, 15, -16];
+        let v = V128::from_i8x16(lanes);
+        assert_eq!(v.as_i8x16(), lanes);
+
+        let v = V128::splat(Lane::I8(7));
+        assert_eq!(v.as_i8x16(), [7i8; 16]);
+        assert!(matches!(v.lane(Shape::I8x16, 3), Lane::I8(7)));
+    }
+
+    #[test]
+    fn v128_u8x16_roundtrip() {
+        let lanes = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let v = V128::from_u8x16(lanes);
+        assert_eq!(v.as_u8x16(), lanes);
+
+        let v = V128::splat(Lane::U8(7));
+        assert_eq!(v.as_u8x16(), [7u8; 16]);
+        assert!(matches!(v.lane(Shape::U8x16, 3), Lane::U8(7)));
+    }
+
+    #[test]
+    fn v128_i16x8_roundtrip() {
+        let lanes = [1i16, -2, 3, -4, 5, -6, 7, -8];
+        let v = V128::from_i16x8(lanes);
+        assert_eq!(v.as_i16x8(), lanes);
+
+        let v = V128::splat(Lane::I16(7));
+        assert_eq!(v.as_i16x8(), [7i16; 8]);
+        assert!(matches!(v.lane(Shape::I16x8, 3), Lane::I16(7)));
+    }
+
+    #[test]
+    fn v128_u16x8_roundtrip() {
+        let lanes = [1u16, 2, 3, 4, 5, 6, 7, 8];
+        let v = V128::from_u16x8(lanes);
+        assert_eq!(v.as_u16x8(), lanes);
+
+        let v = V128::splat(Lane::U16(7));
+        assert_eq!(v.as_u16x8(), [7u16; 8]);
+        assert!(matches!(v.lane(Shape::U16x8, 3), Lane::U16(7)));
+    }
+
+    #[test]
+    fn v128_i32x4_roundtrip() {
+        let lanes = [1i32, -2, 3, -4];
+        let v = V128::from_i32x4(lanes);
+        assert_eq!(v.as_i32x4(), lanes);
+
+        let v = V128::splat(Lane::I32(7));
+        assert_eq!(v.as_i32x4(), [7i32; 4]);
+        assert!(matches!(v.lane(Shape::I32x4, 3), Lane::I32(7)));
+    }
+
+    #[test]
+    fn v128_u32x4_roundtrip() {
+        let lanes = [1u32, 2, 3, 4];
+        let v = V128::from_u32x4(lanes);
+        assert_eq!(v.as_u32x4(), lanes);
+
+        let v = V128::splat(Lane::U32(7));
+        assert_eq!(v.as_u32x4(), [7u32; 4]);
+        assert!(matches!(v.lane(Shape::U32x4, 3), Lane::U32(7)));
+    }
+
+    #[test]
+    fn v128_i64x2_roundtrip() {
+        let lanes = [1i64, -2];
+        let v = V128::from_i64x2(lanes);
+        assert_eq!(v.as_i64x2(), lanes);
+
+        let v = V128::splat(Lane::I64(7));
+        assert_eq!(v.as_i64x2(), [7i64; 2]);
+        assert!(matches!(v.lane(Shape::I64x2, 1), Lane::I64(7)));
+    }
+
+    #[test]
+    fn v128_u64x2_roundtrip() {
+        let lanes = [1u64, 2];
+        let v = V128::from_u64x2(lanes);
+        assert_eq!(v.as_u64x2(), lanes);
+
+        let v = V128::splat(Lane::U64(7));
+        assert_eq!(v.as_u64x2(), [7u64; 2]);
+        assert!(matches!(v.lane(Shape::U64x2, 1), Lane::U64(7)));
+    }
+
+    #[test]
+    fn v128_f32x4_roundtrip() {
+        let lanes = [1.0f32, -2.0, 3.0, -4.0];
+        let v = V128::from_f32x4(lanes);
+        assert_eq!(v.as_f32x4(), lanes);
+
+        let v = V128::splat(Lane::F32(7.0));
+        assert_eq!(v.as_f32x4(), [7.0f32; 4]);
+        assert!(matches!(v.lane(Shape::F32x4, 3), Lane::F32(x) if x == 7.0));
+    }
+
+    #[test]
+    fn v128_f64x2_roundtrip() {
+        let lanes = [1.0f64, -2.0];
+        let v = V128::from_f64x2(lanes);
+        assert_eq!(v.as_f64x2(), lanes);
+
+        let v = V128::splat(Lane::F64(7.0));
+        assert_eq!(v.as_f64x2(), [7.0f64; 2]);
+        assert!(matches!(v.lane(Shape::F64x2, 1), Lane::F64(x) if x == 7.0));
+    }
+}